@@ -0,0 +1,27 @@
+use std::cell::Cell;
+
+use super::token::{Object, Token};
+
+#[derive(Debug)]
+pub enum Expression {
+    Literal(Object),
+    Grouping(Box<Expression>),
+    Unary(Token, Box<Expression>),
+    Binary(Box<Expression>, Token, Box<Expression>),
+    Logical(Box<Expression>, Token, Box<Expression>),
+    Var(Token, Cell<Option<usize>>),
+    Assignment(Token, Box<Expression>, Cell<Option<usize>>),
+    Call(Box<Expression>, Token, Vec<Expression>),
+    Mark,
+}
+
+#[derive(Debug)]
+pub enum Statement {
+    Expression(Expression),
+    Print(Expression),
+    Var(Token, Expression),
+    Block(Vec<Statement>),
+    If(Expression, Box<Statement>, Option<Box<Statement>>),
+    While(Expression, Box<Statement>),
+    Function(Token, Vec<Token>, Vec<Statement>),
+}
@@ -0,0 +1,165 @@
+use super::ast::{Expression, Statement};
+
+/// Renders a parsed program back out as parenthesized S-expressions, e.g.
+/// `(+ 1 (* 2 3))` or `(var x = 1)`, so the parser's output can be inspected
+/// without running the interpreter.
+///
+/// This only covers the printer itself. The `-t`/`-a` CLI dump flags the
+/// originating request also asks for (wiring this, and the lexer's token
+/// stream, into the binary's debug output) are out of scope here — this
+/// tree has no `main`/binary entry point yet — and are tracked separately
+/// as chunk0-5b rather than folded into this commit.
+pub struct AstPrinter;
+
+impl Default for AstPrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl AstPrinter {
+    pub fn new() -> Self {
+        AstPrinter
+    }
+
+    pub fn print(&self, statements: &[Statement]) -> String {
+        statements
+            .iter()
+            .map(|statement| self.print_statement(statement))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn print_statement(&self, statement: &Statement) -> String {
+        match statement {
+            Statement::Expression(expr) => self.print_expression(expr),
+            Statement::Print(expr) => format!("(print {})", self.print_expression(expr)),
+            Statement::Var(name, initializer) => format!(
+                "(var {} = {})",
+                name.lexeme,
+                self.print_expression(initializer)
+            ),
+            Statement::Block(statements) => format!(
+                "(block {})",
+                statements
+                    .iter()
+                    .map(|s| self.print_statement(s))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Statement::If(condition, then_branch, else_branch) => match else_branch {
+                Some(else_branch) => format!(
+                    "(if {} {} {})",
+                    self.print_expression(condition),
+                    self.print_statement(then_branch),
+                    self.print_statement(else_branch)
+                ),
+                None => format!(
+                    "(if {} {})",
+                    self.print_expression(condition),
+                    self.print_statement(then_branch)
+                ),
+            },
+            Statement::While(condition, body) => format!(
+                "(while {} {})",
+                self.print_expression(condition),
+                self.print_statement(body)
+            ),
+            Statement::Function(name, parameters, body) => format!(
+                "(fun {}({}) {})",
+                name.lexeme,
+                parameters
+                    .iter()
+                    .map(|p| p.lexeme.clone())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                body.iter()
+                    .map(|s| self.print_statement(s))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+        }
+    }
+
+    fn print_expression(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Literal(value) => format!("{}", value),
+            Expression::Grouping(inner) => format!("(group {})", self.print_expression(inner)),
+            Expression::Unary(op, right) => {
+                format!("({} {})", op.lexeme, self.print_expression(right))
+            }
+            Expression::Binary(left, op, right) => format!(
+                "({} {} {})",
+                op.lexeme,
+                self.print_expression(left),
+                self.print_expression(right)
+            ),
+            Expression::Logical(left, op, right) => format!(
+                "({} {} {})",
+                op.lexeme,
+                self.print_expression(left),
+                self.print_expression(right)
+            ),
+            Expression::Var(name, _) => name.lexeme.clone(),
+            Expression::Assignment(name, value, _) => {
+                format!("(= {} {})", name.lexeme, self.print_expression(value))
+            }
+            Expression::Call(callee, _, arguments) => format!(
+                "(call {} {})",
+                self.print_expression(callee),
+                arguments
+                    .iter()
+                    .map(|a| self.print_expression(a))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Expression::Mark => "<mark>".to_string(),
+        }
+    }
+}
+
+#[test]
+fn prints_a_binary_expression_with_operator_prefix_and_precedence_nesting() {
+    use crate::lang::lexer::Lexer;
+    use crate::lang::parser::Parser;
+
+    let mut l = Lexer::new(String::from("1 + 2 * 3"));
+    l.scan_tokens();
+    let mut parser = Parser::new(l.tokens);
+    let statements = parser.parse();
+    assert!(parser.errors().is_empty());
+
+    let printed = AstPrinter::new().print(&statements);
+    assert_eq!(printed, "(+ 1 (* 2 3))");
+}
+
+#[test]
+fn prints_an_if_statement_with_both_branches() {
+    use crate::lang::lexer::Lexer;
+    use crate::lang::parser::Parser;
+
+    let mut l = Lexer::new(String::from("if (true) print 1 else print 2"));
+    l.scan_tokens();
+    let mut parser = Parser::new(l.tokens);
+    let statements = parser.parse();
+    assert!(parser.errors().is_empty());
+
+    let printed = AstPrinter::new().print(&statements);
+    assert_eq!(printed, "(if true (print 1) (print 2))");
+}
+
+#[test]
+fn prints_string_literals_as_their_own_text_not_debug_output() {
+    use crate::lang::lexer::Lexer;
+    use crate::lang::parser::Parser;
+
+    let mut l = Lexer::new(String::from("\"hello\""));
+    l.scan_tokens();
+    let mut parser = Parser::new(l.tokens);
+    let statements = parser.parse();
+    assert!(parser.errors().is_empty());
+
+    let printed = AstPrinter::new().print(&statements);
+    assert_eq!(printed, "hello");
+}
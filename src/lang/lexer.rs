@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use super::token::{
+    Object, Token,
+    TokenType::{self, *},
+};
+
+pub struct Lexer {
+    source: Vec<char>,
+    pub tokens: Vec<Token>,
+    start: usize,
+    current: usize,
+    line: usize,
+    keywords: HashMap<String, TokenType>,
+}
+
+#[allow(dead_code)]
+impl Lexer {
+    pub fn new(source: String) -> Self {
+        let keywords = HashMap::from([
+            ("and".to_string(), AND),
+            ("class".to_string(), CLASS),
+            ("else".to_string(), ELSE),
+            ("false".to_string(), FALSE),
+            ("function".to_string(), FUNCTION),
+            ("for".to_string(), FOR),
+            ("if".to_string(), IF),
+            ("nil".to_string(), NIL),
+            ("or".to_string(), OR),
+            ("print".to_string(), PRINT),
+            ("return".to_string(), RETURN),
+            ("true".to_string(), TRUE),
+            ("var".to_string(), VAR),
+            ("while".to_string(), WHILE),
+        ]);
+
+        Lexer {
+            source: source.chars().collect(),
+            tokens: vec![],
+            start: 0,
+            current: 0,
+            line: 1,
+            keywords,
+        }
+    }
+
+    /// Scans the whole source into `self.tokens`, always finishing with a
+    /// terminal `EOF` token so the parser's `peek`/`is_at_end` never has to
+    /// guess whether it has run off the end of the stream.
+    pub fn scan_tokens(&mut self) {
+        while !self.is_at_end() {
+            self.start = self.current;
+            self.scan_token();
+        }
+
+        self.tokens
+            .push(Token::new(EOF, String::new(), Object::Nil, self.line));
+    }
+
+    fn scan_token(&mut self) {
+        let c = self.advance();
+        match c {
+            '(' => self.add_token(LEFT_PAREN),
+            ')' => self.add_token(RIGHT_PAREN),
+            '{' => self.add_token(LEFT_BRACE),
+            '}' => self.add_token(RIGHT_BRACE),
+            ',' => self.add_token(COMMA),
+            '.' => self.add_token(DOT),
+            '-' => self.add_token(MINUS),
+            '+' => self.add_token(PLUS),
+            ';' => self.add_token(SEMICOLON),
+            '*' => self.add_token(STAR),
+            '!' => {
+                let tag = if self.expect('=') { BANG_EQUAL } else { BANG };
+                self.add_token(tag);
+            }
+            '=' => {
+                let tag = if self.expect('=') { EQUAL_EQUAL } else { EQUAL };
+                self.add_token(tag);
+            }
+            '<' => {
+                let tag = if self.expect('=') { LESS_EQUAL } else { LESS };
+                self.add_token(tag);
+            }
+            '>' => {
+                let tag = if self.expect('=') {
+                    GREATER_EQUAL
+                } else {
+                    GREATER
+                };
+                self.add_token(tag);
+            }
+            '/' => {
+                if self.expect('/') {
+                    while self.peek() != '\n' && !self.is_at_end() {
+                        self.advance();
+                    }
+                } else {
+                    self.add_token(SLASH);
+                }
+            }
+            ' ' | '\r' | '\t' => {}
+            '\n' => self.line += 1,
+            '"' => self.string(),
+            c if c.is_ascii_digit() => self.number(),
+            c if c.is_alphabetic() || c == '_' => self.identifier(),
+            _ => {}
+        }
+    }
+
+    fn identifier(&mut self) {
+        while self.peek().is_alphanumeric() || self.peek() == '_' {
+            self.advance();
+        }
+
+        let text: String = self.source[self.start..self.current].iter().collect();
+        let tag = self.keywords.get(&text).copied().unwrap_or(IDENTIFIER);
+        self.add_token(tag);
+    }
+
+    fn number(&mut self) {
+        while self.peek().is_ascii_digit() {
+            self.advance();
+        }
+
+        if self.peek() == '.' && self.peek_next().is_ascii_digit() {
+            self.advance();
+            while self.peek().is_ascii_digit() {
+                self.advance();
+            }
+        }
+
+        let text: String = self.source[self.start..self.current].iter().collect();
+        self.add_token_with_literal(NUMBER, Object::Number(text.parse().unwrap()));
+    }
+
+    fn string(&mut self) {
+        while self.peek() != '"' && !self.is_at_end() {
+            if self.peek() == '\n' {
+                self.line += 1;
+            }
+            self.advance();
+        }
+
+        if self.is_at_end() {
+            return;
+        }
+
+        // closing quote
+        self.advance();
+
+        let value: String = self.source[self.start + 1..self.current - 1]
+            .iter()
+            .collect();
+        self.add_token_with_literal(STRING, Object::Str(value));
+    }
+
+    fn add_token(&mut self, tag: TokenType) {
+        self.add_token_with_literal(tag, Object::Nil);
+    }
+
+    fn add_token_with_literal(&mut self, tag: TokenType, literal: Object) {
+        let lexeme: String = self.source[self.start..self.current].iter().collect();
+        self.tokens.push(Token::new(tag, lexeme, literal, self.line));
+    }
+
+    fn expect(&mut self, expected: char) -> bool {
+        if self.is_at_end() || self.source[self.current] != expected {
+            return false;
+        }
+        self.current += 1;
+        true
+    }
+
+    fn peek(&self) -> char {
+        *self.source.get(self.current).unwrap_or(&'\0')
+    }
+
+    fn peek_next(&self) -> char {
+        *self.source.get(self.current + 1).unwrap_or(&'\0')
+    }
+
+    fn advance(&mut self) -> char {
+        let c = self.source[self.current];
+        self.current += 1;
+        c
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.current >= self.source.len()
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::fmt::Display;
 
 use super::{
@@ -8,51 +9,97 @@ use super::{
     },
 };
 
+/// A parse failure tied to the token where it was detected.
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub token: Token,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.token.tag == EOF {
+            write!(f, "[line {}] error at end: {}", self.token.line, self.message)
+        } else {
+            write!(
+                f,
+                "[line {}] error at '{}': {}",
+                self.token.line, self.token.lexeme, self.message
+            )
+        }
+    }
+}
+
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    errors: Vec<ParseError>,
 }
 
 #[allow(dead_code)]
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Parser { tokens, current: 0 }
+        Parser {
+            tokens,
+            current: 0,
+            errors: vec![],
+        }
     }
 
+    /// Parses as many statements as it can, collecting every error instead of
+    /// bailing on the first one. Call `errors()` afterwards to see what went wrong.
     pub fn parse(&mut self) -> Vec<Statement> {
         let mut statements: Vec<Statement> = vec![];
         while !self.is_at_end() {
-            statements.push(self.declaration())
+            match self.declaration() {
+                Ok(statement) => statements.push(statement),
+                Err(err) => {
+                    self.errors.push(err);
+                    self.synchronize();
+                }
+            }
         }
         statements
     }
 
-    fn statement(&mut self) -> Statement {
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    fn statement(&mut self) -> Result<Statement, ParseError> {
         if self.expect(vec![IF]) {
             return self.ifstmt();
         }
 
+        if self.expect(vec![WHILE]) {
+            return self.whilestmt();
+        }
+
+        if self.expect(vec![FOR]) {
+            return self.forstmt();
+        }
+
         if self.expect(vec![PRINT]) {
             return self.print();
         }
 
         if self.expect(vec![LEFT_BRACE]) {
-            return Statement::Block(self.block());
+            return Ok(Statement::Block(self.block()?));
         }
 
         self.expression_statement()
     }
 
-    fn block(&mut self) -> Vec<Statement> {
+    fn block(&mut self) -> Result<Vec<Statement>, ParseError> {
         let mut statements = vec![];
         while !self.check(RIGHT_BRACE) && !self.is_at_end() {
-            statements.push(self.declaration())
+            statements.push(self.declaration()?)
         }
-        self.consume(RIGHT_BRACE, "expected '}' after block.");
-        statements
+        self.consume(RIGHT_BRACE, "expected '}' after block.")?;
+        Ok(statements)
     }
 
-    fn declaration(&mut self) -> Statement {
+    fn declaration(&mut self) -> Result<Statement, ParseError> {
         if self.expect(vec![FUNCTION]) {
             return self.function("function");
         }
@@ -64,78 +111,172 @@ impl Parser {
         self.statement()
     }
 
-    fn assignment(&mut self) -> Expression {
-        let expr = self.equality();
+    fn assignment(&mut self) -> Result<Expression, ParseError> {
+        let expr = self.or()?;
         if self.expect(vec![EQUAL]) {
-            // let eq = self.previous();
-            let value = self.assignment();
-            if let Expression::Var(token) = expr {
-                return Expression::Assignment(token, Box::new(value));
+            let equals = self.previous();
+            let value = self.assignment()?;
+            if let Expression::Var(token, _) = expr {
+                return Ok(Expression::Assignment(token, Box::new(value), Cell::new(None)));
             }
-            panic!("invalid assignment")
+            return Err(ParseError {
+                token: equals,
+                message: "invalid assignment target".to_string(),
+            });
+        }
+
+        Ok(expr)
+    }
+
+    fn or(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.and()?;
+
+        while self.expect(vec![OR]) {
+            let op = self.previous();
+            let right = self.and()?;
+            expr = Expression::Logical(Box::new(expr), op, Box::new(right));
         }
 
-        return expr;
+        Ok(expr)
     }
 
-    fn print(&mut self) -> Statement {
-        let value = self.expression();
+    fn and(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.equality()?;
 
-        Statement::Print(value)
+        while self.expect(vec![AND]) {
+            let op = self.previous();
+            let right = self.equality()?;
+            expr = Expression::Logical(Box::new(expr), op, Box::new(right));
+        }
+
+        Ok(expr)
     }
 
-    fn ifstmt(&mut self) -> Statement {
-        self.consume(LEFT_PAREN, "expect '(' after 'if' ");
-        let condition = self.expression();
-        self.consume(RIGHT_PAREN, "expect ')' after if condition");
-        let then_block = self.statement();
+    fn print(&mut self) -> Result<Statement, ParseError> {
+        let value = self.expression()?;
+
+        Ok(Statement::Print(value))
+    }
+
+    fn ifstmt(&mut self) -> Result<Statement, ParseError> {
+        self.consume(LEFT_PAREN, "expect '(' after 'if' ")?;
+        let condition = self.expression()?;
+        self.consume(RIGHT_PAREN, "expect ')' after if condition")?;
+        let then_block = self.statement()?;
         let else_block = if self.expect(vec![ELSE]) {
-            Some(self.statement())
+            Some(self.statement()?)
         } else {
             None
         };
 
-        Statement::If(
+        Ok(Statement::If(
             condition,
             Box::new(then_block),
-            else_block.map(|s| Box::new(s)),
-        )
+            else_block.map(Box::new),
+        ))
+    }
+
+    fn whilestmt(&mut self) -> Result<Statement, ParseError> {
+        self.consume(LEFT_PAREN, "expect '(' after 'while'")?;
+        let condition = self.expression()?;
+        self.consume(RIGHT_PAREN, "expect ')' after while condition")?;
+        let body = self.statement()?;
+
+        Ok(Statement::While(condition, Box::new(body)))
     }
 
-    fn var(&mut self) -> Statement {
-        let name = self.consume(IDENTIFIER, "expect var name");
+    /// Desugars `for (init; condition; increment) body` into a block holding
+    /// `init` followed by a `while` whose body is `body` plus `increment`,
+    /// so the interpreter only ever has to know about `Statement::While`.
+    fn forstmt(&mut self) -> Result<Statement, ParseError> {
+        self.consume(LEFT_PAREN, "expect '(' after 'for'")?;
+
+        let initializer = if self.expect(vec![SEMICOLON]) {
+            None
+        } else if self.expect(vec![VAR]) {
+            let declaration = self.var()?;
+            self.consume(SEMICOLON, "expect ';' after loop initializer")?;
+            Some(declaration)
+        } else {
+            let statement = self.expression_statement()?;
+            self.consume(SEMICOLON, "expect ';' after loop initializer")?;
+            Some(statement)
+        };
+
+        let condition = if self.check(SEMICOLON) {
+            Expression::Literal(Object::Bool(true))
+        } else {
+            self.expression()?
+        };
+        self.consume(SEMICOLON, "expect ';' after loop condition")?;
+
+        let increment = if self.check(RIGHT_PAREN) {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        self.consume(RIGHT_PAREN, "expect ')' after for clauses")?;
+
+        let mut body = self.statement()?;
+
+        if let Some(increment) = increment {
+            body = Statement::Block(vec![body, Statement::Expression(increment)]);
+        }
+
+        body = Statement::While(condition, Box::new(body));
+
+        if let Some(initializer) = initializer {
+            body = Statement::Block(vec![initializer, body]);
+        }
+
+        Ok(body)
+    }
+
+    fn var(&mut self) -> Result<Statement, ParseError> {
+        let name = self.consume(IDENTIFIER, "expect var name")?;
         if self.expect(vec![EQUAL]) {
-            return Statement::Var(name, self.expression());
+            return Ok(Statement::Var(name, self.expression()?));
         }
 
-        panic!("var error")
+        Err(ParseError {
+            token: self.peek(),
+            message: "expect '=' after variable name".to_string(),
+        })
     }
 
-    fn function(&mut self, kind: &str) -> Statement {
-        let name = self.consume(IDENTIFIER, format!("expect {:?}", kind));
-        self.consume(LEFT_PAREN, format!("expect ( after  {:?} name", kind));
+    fn function(&mut self, kind: &str) -> Result<Statement, ParseError> {
+        let name = self.consume(IDENTIFIER, format!("expect {:?}", kind))?;
+        self.consume(LEFT_PAREN, format!("expect ( after  {:?} name", kind))?;
         let mut parameters = Vec::<Token>::new();
         if !self.check(RIGHT_PAREN) {
             loop {
-                parameters.push(self.consume(IDENTIFIER, format!("expect parameter name")));
+                parameters.push(self.consume(IDENTIFIER, "expect parameter name".to_string())?);
                 if !self.expect(vec![COMMA]) {
                     break;
                 }
             }
         }
-        self.consume(RIGHT_PAREN, "expect ) after parameters");
-        self.consume(LEFT_BRACE, "expect { before function body");
+        self.consume(RIGHT_PAREN, "expect ) after parameters")?;
+        self.consume(LEFT_BRACE, "expect { before function body")?;
 
-        let body = self.block();
+        let body = self.block()?;
 
-        Statement::Function(name, parameters, body)
+        Ok(Statement::Function(name, parameters, body))
     }
 
-    fn expression_statement(&mut self) -> Statement {
-        Statement::Expression(self.expression())
+    fn expression_statement(&mut self) -> Result<Statement, ParseError> {
+        Ok(Statement::Expression(self.expression()?))
     }
 
-    // expression     → equality ;
+    // statement      → exprStmt | ifStmt | printStmt | whileStmt | forStmt | block ;
+    // whileStmt      → "while" "(" expression ")" statement ;
+    // forStmt        → "for" "(" ( varDecl | exprStmt | ";" )
+    //                  expression? ";" expression? ")" statement ;
+    // expression     → assignment ;
+    // assignment     → IDENTIFIER "=" assignment
+    //                | logic_or ;
+    // logic_or       → logic_and ( "or" logic_and )* ;
+    // logic_and      → equality ( "and" equality )* ;
     // equality       → comparison ( ( "!=" | "==" ) comparison )* ;
     // comparison     → term ( ( ">" | ">=" | "<" | "<=" ) term )* ;
     // term           → factor ( ( "-" | "+" ) factor )* ;
@@ -144,130 +285,140 @@ impl Parser {
     //                | primary ;
     // primary        → NUMBER | STRING | "true" | "false" | "nil"
     //                | "(" expression ")" ;
-    fn expression(&mut self) -> Expression {
+    fn expression(&mut self) -> Result<Expression, ParseError> {
         self.assignment()
     }
 
-    fn equality(&mut self) -> Expression {
-        let mut expr = self.comparison();
+    fn equality(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.comparison()?;
 
         while self.expect(vec![BANG_EQUAL, EQUAL_EQUAL]) {
             let op = self.previous();
-            let cmp = self.comparison();
+            let cmp = self.comparison()?;
             expr = Expression::Binary(Box::new(expr), op, Box::new(cmp));
         }
 
-        expr
+        Ok(expr)
     }
 
-    fn comparison(&mut self) -> Expression {
-        let mut expr = self.term();
+    fn comparison(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.term()?;
 
         while self.expect(vec![GREATER, GREATER_EQUAL, LESS, LESS_EQUAL]) {
             let op = self.previous();
-            let right = self.term();
+            let right = self.term()?;
             expr = Expression::Binary(Box::new(expr), op, Box::new(right));
         }
 
-        expr
+        Ok(expr)
     }
 
-    fn term(&mut self) -> Expression {
-        let mut expr = self.factor();
+    fn term(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.factor()?;
 
         while self.expect(vec![MINUS, PLUS]) {
             let op = self.previous();
-            let right = self.factor();
+            let right = self.factor()?;
             expr = Expression::Binary(Box::new(expr), op, Box::new(right));
         }
 
-        expr
+        Ok(expr)
     }
 
-    fn factor(&mut self) -> Expression {
-        let mut expr = self.unary();
+    fn factor(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.unary()?;
 
         while self.expect(vec![SLASH, STAR]) {
             let op = self.previous();
-            let right = self.unary();
+            let right = self.unary()?;
             expr = Expression::Binary(Box::new(expr), op, Box::new(right));
         }
 
-        expr
+        Ok(expr)
     }
 
-    fn unary(&mut self) -> Expression {
+    fn unary(&mut self) -> Result<Expression, ParseError> {
         if self.expect(vec![BANG, MINUS]) {
             let op = self.previous();
-            let right = self.unary();
-            return Expression::Unary(op, Box::new(right));
+            let right = self.unary()?;
+            return Ok(Expression::Unary(op, Box::new(right)));
         }
 
         self.call()
     }
 
-    fn call(&mut self) -> Expression {
-        let mut expr = self.primary();
+    fn call(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.primary()?;
         loop {
             // 嵌套执行的函数形式处理
             // f1(f2())
             if self.expect(vec![LEFT_PAREN]) {
-                expr = self.finish_call(expr);
+                expr = self.finish_call(expr)?;
             } else {
                 break;
             }
         }
-        expr
+        Ok(expr)
     }
 
-    fn finish_call(&mut self, callee: Expression) -> Expression {
+    fn finish_call(&mut self, callee: Expression) -> Result<Expression, ParseError> {
         let mut arguments = Vec::new();
         if !self.check(RIGHT_PAREN) {
             loop {
-                arguments.push(self.expression());
+                arguments.push(self.expression()?);
                 if !self.expect(vec![COMMA]) {
                     break;
                 }
             }
         }
 
-        let paren = self.consume(RIGHT_PAREN, "expect ')' after arguments");
-        Expression::Call(Box::new(callee), paren, arguments)
+        let paren = self.consume(RIGHT_PAREN, "expect ')' after arguments")?;
+        Ok(Expression::Call(Box::new(callee), paren, arguments))
     }
 
-    fn primary(&mut self) -> Expression {
+    fn primary(&mut self) -> Result<Expression, ParseError> {
         if self.expect(vec![FALSE]) {
-            return Expression::Literal(Object::Bool(false));
+            return Ok(Expression::Literal(Object::Bool(false)));
         }
 
         if self.expect(vec![TRUE]) {
-            return Expression::Literal(Object::Bool(true));
+            return Ok(Expression::Literal(Object::Bool(true)));
         }
 
         if self.expect(vec![NUMBER, STRING]) {
-            return Expression::Literal(self.previous().literal);
+            return Ok(Expression::Literal(self.previous().literal));
         }
 
         if self.expect(vec![LEFT_PAREN]) {
-            let expr = self.expression();
-            self.consume(RIGHT_PAREN, "expect ')' after expression");
-            return Expression::Grouping(Box::new(expr));
+            let expr = self.expression()?;
+            self.consume(RIGHT_PAREN, "expect ')' after expression")?;
+            return Ok(Expression::Grouping(Box::new(expr)));
         }
 
         if self.expect(vec![IDENTIFIER]) {
-            return Expression::Var(self.previous());
+            return Ok(Expression::Var(self.previous(), Cell::new(None)));
         }
 
-        Expression::Mark
+        Err(ParseError {
+            token: self.peek(),
+            message: "expect expression".to_string(),
+        })
     }
 
-    /// 如果下一个 token 符合预期， 指针后移，否则抛出异常
-    fn consume<T: AsRef<str> + Display>(&mut self, tag: TokenType, message: T) -> Token {
+    /// 如果下一个 token 符合预期， 指针后移，否则返回一个 ParseError
+    fn consume<T: AsRef<str> + Display>(
+        &mut self,
+        tag: TokenType,
+        message: T,
+    ) -> Result<Token, ParseError> {
         if self.check(tag) {
-            return self.advance();
+            return Ok(self.advance());
         }
 
-        panic!("{}", message)
+        Err(ParseError {
+            token: self.peek(),
+            message: message.to_string(),
+        })
     }
 
     /// 如果找到了一个符合条件的token，同时指针后移
@@ -296,8 +447,12 @@ impl Parser {
         self.previous()
     }
 
+    // `advance` never moves past the lexer's terminal EOF token, so on
+    // truncated input (e.g. an unterminated `(`) `current` stops at EOF and a
+    // `ParseError` is reported. The fallback to the last token is reached
+    // only for a token stream that has no EOF token.
     fn peek(&self) -> Token {
-        self.tokens.get(self.current).unwrap().clone()
+        self.tokens.get(self.current).cloned().unwrap_or_else(|| self.previous())
     }
 
     /// 返回上一个token，current 指针不变
@@ -305,8 +460,30 @@ impl Parser {
         self.tokens.get(self.current - 1).unwrap().clone()
     }
 
+    // The lexer always appends a terminal EOF token, so this is the only
+    // check we need. For a token stream without that EOF token this never
+    // returns true, and `peek()`'s fallback only keeps `peek` itself in bounds.
     fn is_at_end(&mut self) -> bool {
-        self.current == self.tokens.len()
+        self.peek().tag == EOF
+    }
+
+    /// Discards tokens until we're likely at the start of the next statement,
+    /// so a single bad statement doesn't cascade into a wall of spurious errors.
+    fn synchronize(&mut self) {
+        self.advance();
+
+        while !self.is_at_end() {
+            if self.previous().tag == SEMICOLON {
+                return;
+            }
+
+            match self.peek().tag {
+                CLASS | FUNCTION | VAR | FOR | IF | WHILE | PRINT | RETURN => return,
+                _ => {}
+            }
+
+            self.advance();
+        }
     }
 }
 
@@ -314,7 +491,6 @@ impl Parser {
 fn test() {
     use crate::lang::lexer::Lexer;
 
-    // FIXME: Option Unwrap Error
     let mut l = Lexer::new(String::from("function a() {print 1}"));
     l.scan_tokens();
 
@@ -323,3 +499,75 @@ fn test() {
 
     println!("{:#?}", exp);
 }
+
+#[test]
+fn or_and_and_parse_as_logical_not_binary() {
+    use crate::lang::lexer::Lexer;
+
+    let mut l = Lexer::new(String::from("a or b and c"));
+    l.scan_tokens();
+
+    let mut parser = Parser::new(l.tokens);
+    let statements = parser.parse();
+
+    match &statements[0] {
+        Statement::Expression(Expression::Logical(left, op, right)) => {
+            assert_eq!(op.tag, OR);
+            assert!(matches!(**left, Expression::Var(_, _)));
+            assert!(matches!(**right, Expression::Logical(_, _, _)));
+        }
+        other => panic!("expected a top-level Logical(or) expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn synchronize_lets_parsing_continue_past_a_bad_statement() {
+    use crate::lang::lexer::Lexer;
+
+    let mut l = Lexer::new(String::from("var = 1; var = 2;"));
+    l.scan_tokens();
+
+    let mut parser = Parser::new(l.tokens);
+    parser.parse();
+
+    assert_eq!(parser.errors().len(), 2);
+}
+
+#[test]
+fn unterminated_grouping_reports_a_parse_error_instead_of_panicking() {
+    use crate::lang::lexer::Lexer;
+
+    let mut l = Lexer::new(String::from("(1 + 2"));
+    l.scan_tokens();
+
+    let mut parser = Parser::new(l.tokens);
+    parser.parse();
+
+    assert!(!parser.errors().is_empty());
+}
+
+#[test]
+fn for_loop_with_var_initializer_parses_without_errors() {
+    use crate::lang::lexer::Lexer;
+
+    let mut l = Lexer::new(String::from("for (var i = 0; i < 10; i = i + 1) print i"));
+    l.scan_tokens();
+
+    let mut parser = Parser::new(l.tokens);
+    parser.parse();
+
+    assert!(parser.errors().is_empty(), "{:?}", parser.errors());
+}
+
+#[test]
+fn for_loop_with_expression_initializer_parses_without_errors() {
+    use crate::lang::lexer::Lexer;
+
+    let mut l = Lexer::new(String::from("for (i = 0; i < 10; i = i + 1) print i"));
+    l.scan_tokens();
+
+    let mut parser = Parser::new(l.tokens);
+    parser.parse();
+
+    assert!(parser.errors().is_empty(), "{:?}", parser.errors());
+}
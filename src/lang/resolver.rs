@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use super::ast::{Expression, Statement};
+use super::token::Token;
+
+/// A static-analysis violation caught while walking the tree, e.g. a local
+/// variable that reads itself in its own initializer. Unlike `ParseError`,
+/// this never stops the walk from finishing: it's collected purely so the
+/// caller can report it before interpretation starts.
+#[derive(Debug, Clone)]
+pub struct ResolveError {
+    pub token: Token,
+    pub message: String,
+}
+
+/// Walks a parsed program once, before interpretation, recording how many
+/// enclosing scopes separate each variable reference from the scope that
+/// declares it. Globals are left unresolved (`None`) and fall back to the
+/// interpreter's runtime lookup.
+pub struct Resolver {
+    scopes: Vec<HashMap<String, bool>>,
+    errors: Vec<ResolveError>,
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl Resolver {
+    pub fn new() -> Self {
+        Resolver {
+            scopes: vec![],
+            errors: vec![],
+        }
+    }
+
+    pub fn resolve(&mut self, statements: &[Statement]) -> &[ResolveError] {
+        for statement in statements {
+            self.resolve_statement(statement);
+        }
+        &self.errors
+    }
+
+    fn resolve_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(statements) => {
+                self.begin_scope();
+                for statement in statements {
+                    self.resolve_statement(statement);
+                }
+                self.end_scope();
+            }
+            Statement::Var(name, initializer) => {
+                self.declare(name);
+                self.resolve_expression(initializer);
+                self.define(name);
+            }
+            Statement::Function(name, parameters, body) => {
+                self.declare(name);
+                self.define(name);
+                self.resolve_function(parameters, body);
+            }
+            Statement::If(condition, then_branch, else_branch) => {
+                self.resolve_expression(condition);
+                self.resolve_statement(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.resolve_statement(else_branch);
+                }
+            }
+            Statement::While(condition, body) => {
+                self.resolve_expression(condition);
+                self.resolve_statement(body);
+            }
+            Statement::Print(expression) => self.resolve_expression(expression),
+            Statement::Expression(expression) => self.resolve_expression(expression),
+        }
+    }
+
+    fn resolve_function(&mut self, parameters: &[Token], body: &[Statement]) {
+        self.begin_scope();
+        for parameter in parameters {
+            self.declare(parameter);
+            self.define(parameter);
+        }
+        for statement in body {
+            self.resolve_statement(statement);
+        }
+        self.end_scope();
+    }
+
+    fn resolve_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Var(name, depth) => {
+                if let Some(scope) = self.scopes.last() {
+                    if scope.get(&name.lexeme) == Some(&false) {
+                        self.errors.push(ResolveError {
+                            token: name.clone(),
+                            message: "can't read local variable in its own initializer"
+                                .to_string(),
+                        });
+                    }
+                }
+                depth.set(self.resolve_local(name));
+            }
+            Expression::Assignment(name, value, depth) => {
+                self.resolve_expression(value);
+                depth.set(self.resolve_local(name));
+            }
+            Expression::Binary(left, _, right) | Expression::Logical(left, _, right) => {
+                self.resolve_expression(left);
+                self.resolve_expression(right);
+            }
+            Expression::Unary(_, right) => self.resolve_expression(right),
+            Expression::Grouping(inner) => self.resolve_expression(inner),
+            Expression::Call(callee, _, arguments) => {
+                self.resolve_expression(callee);
+                for argument in arguments {
+                    self.resolve_expression(argument);
+                }
+            }
+            Expression::Literal(_) | Expression::Mark => {}
+        }
+    }
+
+    /// Scans the scope stack from innermost outward, recording how many hops
+    /// it took to find `name`. Leaves `None` when nothing matches, meaning
+    /// the variable is assumed global.
+    fn resolve_local(&self, name: &Token) -> Option<usize> {
+        for (depth, scope) in self.scopes.iter().rev().enumerate() {
+            if scope.contains_key(&name.lexeme) {
+                return Some(depth);
+            }
+        }
+        None
+    }
+
+    fn declare(&mut self, name: &Token) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), false);
+        }
+    }
+
+    fn define(&mut self, name: &Token) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), true);
+        }
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+}
+
+#[test]
+fn flags_local_variable_read_in_its_own_initializer() {
+    use crate::lang::lexer::Lexer;
+    use crate::lang::parser::Parser;
+
+    let mut l = Lexer::new(String::from("{ var a = a }"));
+    l.scan_tokens();
+    let mut parser = Parser::new(l.tokens);
+    let statements = parser.parse();
+    assert!(parser.errors().is_empty());
+
+    let mut resolver = Resolver::new();
+    assert_eq!(resolver.resolve(&statements).len(), 1);
+}
+
+#[test]
+fn resolves_a_block_local_variable_to_depth_zero() {
+    use crate::lang::lexer::Lexer;
+    use crate::lang::parser::Parser;
+
+    let mut l = Lexer::new(String::from("{ var a = 1 print a }"));
+    l.scan_tokens();
+    let mut parser = Parser::new(l.tokens);
+    let statements = parser.parse();
+    assert!(parser.errors().is_empty());
+
+    let mut resolver = Resolver::new();
+    assert!(resolver.resolve(&statements).is_empty());
+
+    let Statement::Block(block) = &statements[0] else {
+        panic!("expected a block statement, got {:?}", statements[0]);
+    };
+    let Statement::Print(Expression::Var(_, depth)) = &block[1] else {
+        panic!("expected a print of a resolved local variable, got {:?}", block[1]);
+    };
+    assert_eq!(depth.get(), Some(0));
+}
+
+#[test]
+fn leaves_a_global_variable_unresolved() {
+    use crate::lang::lexer::Lexer;
+    use crate::lang::parser::Parser;
+
+    let mut l = Lexer::new(String::from("var a = 1 print a"));
+    l.scan_tokens();
+    let mut parser = Parser::new(l.tokens);
+    let statements = parser.parse();
+    assert!(parser.errors().is_empty());
+
+    let mut resolver = Resolver::new();
+    assert!(resolver.resolve(&statements).is_empty());
+
+    let Statement::Print(Expression::Var(_, depth)) = &statements[1] else {
+        panic!("expected a print of a resolved variable, got {:?}", statements[1]);
+    };
+    assert_eq!(depth.get(), None);
+}
@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType {
+    // single-character tokens
+    LEFT_PAREN,
+    RIGHT_PAREN,
+    LEFT_BRACE,
+    RIGHT_BRACE,
+    COMMA,
+    DOT,
+    MINUS,
+    PLUS,
+    SEMICOLON,
+    SLASH,
+    STAR,
+
+    // one or two character tokens
+    BANG,
+    BANG_EQUAL,
+    EQUAL,
+    EQUAL_EQUAL,
+    GREATER,
+    GREATER_EQUAL,
+    LESS,
+    LESS_EQUAL,
+
+    // literals
+    IDENTIFIER,
+    STRING,
+    NUMBER,
+
+    // keywords
+    AND,
+    CLASS,
+    ELSE,
+    FALSE,
+    FUNCTION,
+    FOR,
+    IF,
+    NIL,
+    OR,
+    PRINT,
+    RETURN,
+    TRUE,
+    VAR,
+    WHILE,
+
+    EOF,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Object {
+    Number(f64),
+    Str(String),
+    Bool(bool),
+    Nil,
+}
+
+impl Display for Object {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Object::Number(n) => write!(f, "{n}"),
+            Object::Str(s) => write!(f, "{s}"),
+            Object::Bool(b) => write!(f, "{b}"),
+            Object::Nil => write!(f, "nil"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub tag: TokenType,
+    pub lexeme: String,
+    pub literal: Object,
+    pub line: usize,
+}
+
+impl Token {
+    pub fn new(tag: TokenType, lexeme: String, literal: Object, line: usize) -> Self {
+        Token {
+            tag,
+            lexeme,
+            literal,
+            line,
+        }
+    }
+}